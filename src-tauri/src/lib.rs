@@ -86,7 +86,8 @@ pub fn run() {
             mock_server::start_mock_server, 
             mock_server::stop_mock_server,
             mock_server::stop_all_mock_servers,
-            mock_server::get_running_servers
+            mock_server::get_running_servers,
+            mock_server::get_recorded_routes
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,12 +1,16 @@
 use std::sync::{Arc, Mutex};
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{HashMap, HashSet};
 use std::net::TcpListener;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::oneshot;
 use warp::Filter;
 use tauri::{State, Window, Emitter};
 use hmac::{Hmac, Mac};
 use sha2::{Sha256, Digest};
 
+type HmacSha256 = Hmac<Sha256>;
+
 // Auth Configuration - supports all auth types
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, Default)]
 pub struct AuthConfig {
@@ -16,6 +20,13 @@ pub struct AuthConfig {
     
     // Bearer & OAuth2
     pub token: Option<String>,
+
+    // OAuth2 token endpoint (auth_type "oauth2_token_endpoint")
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+    pub grant_type: Option<String>,
+    pub token_ttl: Option<u64>,
     
     // API Key
     pub header: Option<String>,
@@ -45,6 +56,16 @@ pub struct AuthConfig {
     pub hawk_algorithm: Option<String>,
 }
 
+// TLS configuration for serving a mock over HTTPS
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, Default)]
+pub struct TlsConfig {
+    // Serve over HTTPS when true
+    pub enabled: bool,
+    // Optional PEM cert/key paths; when omitted a self-signed cert is generated
+    pub cert_path: Option<String>,
+    pub key_path: Option<String>,
+}
+
 // Route Definitions
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct MockRoute {
@@ -58,33 +79,69 @@ pub struct MockRoute {
     pub auth_type: Option<String>,
     #[serde(default)]
     pub auth_config: Option<AuthConfig>,
+    // When true (and an upstream is configured), forward this route to the real API
+    #[serde(default)]
+    pub proxy: bool,
 }
 
 // Info about a running server instance
 struct RunningServer {
     shutdown_tx: oneshot::Sender<()>,
     port: u16,
+    scheme: String,
+    // Upstream base URL this server records/replays against, if any
+    upstream: Option<String>,
+}
+
+// Public view of a running server, returned to the frontend
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RunningServerInfo {
+    pub id: String,
+    pub port: u16,
+    pub scheme: String,
+    pub url: String,
 }
 
 // Server State to hold multiple active servers
 pub struct MockServerState {
     // Map of server_id -> running server info
     pub servers: Arc<Mutex<HashMap<String, RunningServer>>>,
+    // Nonces issued by digest challenges, consumed the first time a follow-up presents them to catch replays
+    pub digest_nonces: Arc<Mutex<HashSet<String>>>,
+    // Routes captured while proxying to an upstream, available for later replay
+    pub recorded_routes: Arc<Mutex<Vec<MockRoute>>>,
 }
 
 impl MockServerState {
     pub fn new() -> Self {
         Self {
             servers: Arc::new(Mutex::new(HashMap::new())),
+            digest_nonces: Arc::new(Mutex::new(HashSet::new())),
+            recorded_routes: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
 
+// Monotonic counter folded into generated nonces so concurrent challenges stay unique
+static NONCE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// Generate an opaque random-looking token for digest nonces/opaque values
+fn generate_nonce() -> String {
+    let n = NONCE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{:x}", md5::compute(format!("{}-{}", nanos, n).as_bytes()))
+}
+
 #[tauri::command]
 pub async fn start_mock_server(
     server_id: String,
     port: u16,
     routes: Vec<MockRoute>,
+    tls: Option<TlsConfig>,
+    upstream: Option<String>,
     state: State<'_, MockServerState>,
     window: Window,
 ) -> Result<String, String> {
@@ -103,6 +160,33 @@ pub async fn start_mock_server(
         }
     }
 
+    // 2b. Resolve TLS material (cert+key PEM bytes) if HTTPS was requested
+    let tls_setup: Option<(Vec<u8>, Vec<u8>)> = match &tls {
+        Some(cfg) if cfg.enabled => {
+            match (&cfg.cert_path, &cfg.key_path) {
+                (Some(cert_path), Some(key_path)) => {
+                    let cert = std::fs::read(cert_path)
+                        .map_err(|e| format!("Failed to read TLS cert {}: {}", cert_path, e))?;
+                    let key = std::fs::read(key_path)
+                        .map_err(|e| format!("Failed to read TLS key {}: {}", key_path, e))?;
+                    Some((cert, key))
+                }
+                // No cert/key supplied: mint a self-signed cert for localhost
+                _ => {
+                    let cert = rcgen::generate_simple_self_signed(
+                        vec!["localhost".to_string(), "127.0.0.1".to_string()],
+                    )
+                    .map_err(|e| format!("Failed to generate self-signed cert: {}", e))?;
+                    let cert_pem = cert.serialize_pem().map_err(|e| e.to_string())?;
+                    let key_pem = cert.serialize_private_key_pem();
+                    Some((cert_pem.into_bytes(), key_pem.into_bytes()))
+                }
+            }
+        }
+        _ => None,
+    };
+    let scheme = if tls_setup.is_some() { "https" } else { "http" };
+
     // 3. Store routes in Arc for the filter
     let routes_arc = Arc::new(routes);
     let window_arc = Arc::new(window);
@@ -111,6 +195,9 @@ pub async fn start_mock_server(
     // 4. Define Filter
     let routes_for_filter = routes_arc.clone();
     let window_for_filter = window_arc.clone();
+    let nonces_for_filter = state.digest_nonces.clone();
+    let recorded_for_filter = state.recorded_routes.clone();
+    let upstream_for_filter = upstream.clone();
     let sid_for_log = server_id.clone();
 
     let api = warp::any()
@@ -118,44 +205,84 @@ pub async fn start_mock_server(
         .and(warp::path::full())
         .and(warp::header::headers_cloned())
         .and(warp::body::bytes())
-        .map(move |method: warp::http::Method, path: warp::path::FullPath, req_headers: warp::http::HeaderMap, _body: warp::hyper::body::Bytes| {
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and_then(move |method: warp::http::Method, path: warp::path::FullPath, req_headers: warp::http::HeaderMap, body: warp::hyper::body::Bytes, query: String| {
+            // Re-clone the shared state for this request's async task.
+            let routes_for_filter = routes_for_filter.clone();
+            let window_for_filter = window_for_filter.clone();
+            let nonces_for_filter = nonces_for_filter.clone();
+            let recorded_for_filter = recorded_for_filter.clone();
+            let upstream_for_filter = upstream_for_filter.clone();
+            let sid_for_log = sid_for_log.clone();
+            async move {
             let path_str = path.as_str();
             let method_str = method.as_str();
-            
+
             // Log to Frontend
             let log_msg = format!("[{}:{}] {} {}", sid_for_log, port, method_str, path_str);
             let _ = window_for_filter.emit("mock-request", log_msg);
 
-            // Find matching route
-            let matched = routes_for_filter.iter().find(|r| {
-                if !r.method.eq_ignore_ascii_case(method_str) {
-                    return false;
-                }
-
-                // Check for dynamic path match
-                let route_parts: Vec<&str> = r.path.split('/').filter(|s| !s.is_empty()).collect();
-                let req_parts: Vec<&str> = path_str.split('/').filter(|s| !s.is_empty()).collect();
+            // Find matching route, capturing any `:param` path bindings along the way
+            let matched = routes_for_filter.iter()
+                .find_map(|r| match_route(r, method_str, path_str).map(|params| (r, params)));
 
-                if route_parts.len() != req_parts.len() {
-                    return false;
+            let response: warp::http::Response<String> = if let Some((route, path_params)) = matched {
+                // Routes flagged for proxying fall through to the upstream when one is set.
+                if route.proxy {
+                    if let Some(base) = upstream_for_filter.as_deref() {
+                        return Ok::<_, warp::Rejection>(
+                            proxy_and_record(base, method_str, path_str, &query, &req_headers, &body, &window_for_filter, &recorded_for_filter).await,
+                        );
+                    }
+                }
+                // OAuth2 token endpoint: mint a signed JWT instead of validating one.
+                if route.auth_type.as_deref() == Some("oauth2_token_endpoint") {
+                    if !method_str.eq_ignore_ascii_case("POST") {
+                        return Ok::<_, warp::Rejection>(warp::http::Response::builder()
+                            .status(405)
+                            .header("Access-Control-Allow-Origin", "*")
+                            .body(r#"{"error": "method_not_allowed"}"#.to_string())
+                            .unwrap());
+                    }
+                    let content_type = req_headers.get("content-type")
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or("");
+                    let cfg = route.auth_config.clone().unwrap_or_default();
+                    return Ok::<_, warp::Rejection>(match issue_oauth2_token(&cfg, &body, content_type) {
+                        Ok(json) => warp::http::Response::builder()
+                            .status(200)
+                            .header("Access-Control-Allow-Origin", "*")
+                            .header("Content-Type", "application/json")
+                            .body(json)
+                            .unwrap(),
+                        Err(err) => warp::http::Response::builder()
+                            .status(401)
+                            .header("Access-Control-Allow-Origin", "*")
+                            .header("Content-Type", "application/json")
+                            .body(format!(r#"{{"error": "{}"}}"#, err))
+                            .unwrap(),
+                    });
                 }
 
-                route_parts.iter().zip(req_parts.iter()).all(|(route_part, req_part)| {
-                    route_part.starts_with(':') || route_part == req_part // Match param or exact string
-                })
-            });
-
-            if let Some(route) = matched {
                 // Check auth validation
-                let auth_valid = validate_auth(&route, &req_headers);
-                
-                if !auth_valid {
-                    return warp::http::Response::builder()
+                // Host header without any port suffix, for Hawk MAC normalization
+                let host_str = req_headers.get("host")
+                    .and_then(|v| v.to_str().ok())
+                    .map(|h| h.split(':').next().unwrap_or(h))
+                    .unwrap_or("127.0.0.1");
+                let outcome = validate_auth(&route, &req_headers, method_str, path_str, &query, &body, host_str, port, &nonces_for_filter);
+
+                if let AuthOutcome::Deny | AuthOutcome::Challenge(_) = &outcome {
+                    let mut resp = warp::http::Response::builder()
                         .status(401)
                         .header("Access-Control-Allow-Origin", "*")
-                        .header("Content-Type", "application/json")
+                        .header("Content-Type", "application/json");
+                    if let AuthOutcome::Challenge(www_authenticate) = &outcome {
+                        resp = resp.header("WWW-Authenticate", www_authenticate);
+                    }
+                    return Ok::<_, warp::Rejection>(resp
                         .body(r#"{"error": "Unauthorized", "message": "Invalid or missing authentication"}"#.to_string())
-                        .unwrap();
+                        .unwrap());
                 }
 
                 let mut resp = warp::http::Response::builder()
@@ -172,13 +299,21 @@ pub async fn start_mock_server(
                 resp = resp.header("Access-Control-Allow-Methods", "GET, POST, PUT, DELETE, PATCH, OPTIONS");
                 resp = resp.header("Access-Control-Allow-Headers", "*");
 
-                resp.body(route.body.clone()).unwrap_or_else(|_| warp::http::Response::new("Error building response".into()))
+                // Render `{{param}}`, `{{query.x}}` and `{{body.json.path}}` placeholders
+                let rendered = render_template(&route.body, &path_params, &query, &body);
+                resp.body(rendered).unwrap_or_else(|_| warp::http::Response::new("Error building response".into()))
+            } else if let Some(base) = upstream_for_filter.as_deref() {
+                // No local mock matched: forward to the upstream and record the result.
+                proxy_and_record(base, method_str, path_str, &query, &req_headers, &body, &window_for_filter, &recorded_for_filter).await
             } else {
                 warp::http::Response::builder()
                     .status(404)
                     .header("Access-Control-Allow-Origin", "*")
                     .body(format!("Mock route not found: {} {}", method_str, path_str))
                     .unwrap()
+            };
+
+            Ok::<_, warp::Rejection>(response)
             }
         });
 
@@ -188,19 +323,32 @@ pub async fn start_mock_server(
     // 5. Store the running server
     {
         let mut servers = state.servers.lock().map_err(|e| e.to_string())?;
-        servers.insert(server_id.clone(), RunningServer { shutdown_tx: tx, port });
+        servers.insert(server_id.clone(), RunningServer { shutdown_tx: tx, port, scheme: scheme.to_string(), upstream: upstream.clone() });
     }
 
-    // 6. Spawn the server
+    // 6. Spawn the server (HTTPS when TLS material is present, otherwise plain HTTP)
     tokio::spawn(async move {
-        let (_addr, server) = warp::serve(api)
-            .bind_with_graceful_shutdown(([127, 0, 0, 1], port), async {
-                rx.await.ok();
-            });
-        server.await;
+        let shutdown = async {
+            rx.await.ok();
+        };
+        match tls_setup {
+            Some((cert, key)) => {
+                let (_addr, server) = warp::serve(api)
+                    .tls()
+                    .cert(cert)
+                    .key(key)
+                    .bind_with_graceful_shutdown(([127, 0, 0, 1], port), shutdown);
+                server.await;
+            }
+            None => {
+                let (_addr, server) = warp::serve(api)
+                    .bind_with_graceful_shutdown(([127, 0, 0, 1], port), shutdown);
+                server.await;
+            }
+        }
     });
 
-    Ok(format!("Server {} started on port {}", server_id_clone, port))
+    Ok(format!("Server {} started on {}://127.0.0.1:{}", server_id_clone, scheme, port))
 }
 
 // Stop a specific server by ID
@@ -233,36 +381,163 @@ pub async fn stop_all_mock_servers(state: State<'_, MockServerState>) -> Result<
     Ok(format!("Stopped {} servers", count))
 }
 
-// Get list of running server IDs
+// Get list of running servers, including the scheme/URL each is served on
 #[tauri::command]
-pub async fn get_running_servers(state: State<'_, MockServerState>) -> Result<Vec<String>, String> {
+pub async fn get_running_servers(state: State<'_, MockServerState>) -> Result<Vec<RunningServerInfo>, String> {
     let servers = state.servers.lock().map_err(|e| e.to_string())?;
-    Ok(servers.keys().cloned().collect())
+    Ok(servers
+        .iter()
+        .map(|(id, server)| RunningServerInfo {
+            id: id.clone(),
+            port: server.port,
+            scheme: server.scheme.clone(),
+            url: format!("{}://127.0.0.1:{}", server.scheme, server.port),
+        })
+        .collect())
+}
+
+// Result of an auth check: allow the request, deny it, or deny it while
+// returning a `WWW-Authenticate` challenge (used by digest auth).
+enum AuthOutcome {
+    Allow,
+    Deny,
+    Challenge(String),
+}
+
+impl From<bool> for AuthOutcome {
+    fn from(ok: bool) -> Self {
+        if ok { AuthOutcome::Allow } else { AuthOutcome::Deny }
+    }
+}
+
+// Return the routes captured so far in record mode, so they can be replayed offline.
+#[tauri::command]
+pub async fn get_recorded_routes(state: State<'_, MockServerState>) -> Result<Vec<MockRoute>, String> {
+    let recorded = state.recorded_routes.lock().map_err(|e| e.to_string())?;
+    Ok(recorded.clone())
+}
+
+// Forward a request to the configured upstream, return the real response to the
+// caller, and capture it as a `MockRoute` (emitted to the frontend for later replay).
+async fn proxy_and_record(
+    base: &str,
+    method: &str,
+    path: &str,
+    query: &str,
+    req_headers: &warp::http::HeaderMap,
+    body: &[u8],
+    window: &Window,
+    recorded: &Arc<Mutex<Vec<MockRoute>>>,
+) -> warp::http::Response<String> {
+    let url = if query.is_empty() {
+        format!("{}{}", base.trim_end_matches('/'), path)
+    } else {
+        format!("{}{}?{}", base.trim_end_matches('/'), path, query)
+    };
+
+    let method_parsed = match reqwest::Method::from_bytes(method.as_bytes()) {
+        Ok(m) => m,
+        Err(_) => return proxy_error(&format!("Invalid method: {}", method)),
+    };
+
+    let client = reqwest::Client::new();
+    let mut request = client.request(method_parsed, &url);
+    // Forward the incoming headers, dropping Host so reqwest sets it for the upstream.
+    for (name, value) in req_headers.iter() {
+        if name.as_str().eq_ignore_ascii_case("host") {
+            continue;
+        }
+        if let Ok(v) = value.to_str() {
+            request = request.header(name.as_str(), v);
+        }
+    }
+    if !body.is_empty() {
+        request = request.body(body.to_vec());
+    }
+
+    let upstream_resp = match request.send().await {
+        Ok(r) => r,
+        Err(e) => return proxy_error(&format!("Upstream request failed: {}", e)),
+    };
+
+    let status = upstream_resp.status().as_u16();
+    let mut headers_map = HashMap::new();
+    for (name, value) in upstream_resp.headers() {
+        headers_map.insert(name.to_string(), value.to_str().unwrap_or("").to_string());
+    }
+    let resp_body = upstream_resp.text().await.unwrap_or_default();
+
+    // Capture the exchange as a replayable mock route.
+    let recorded_route = MockRoute {
+        id: generate_nonce(),
+        method: method.to_string(),
+        path: path.to_string(),
+        status,
+        body: resp_body.clone(),
+        headers: Some(headers_map.clone()),
+        auth_type: None,
+        auth_config: None,
+        proxy: false,
+    };
+    if let Ok(mut store) = recorded.lock() {
+        store.push(recorded_route.clone());
+    }
+    let _ = window.emit("mock-route-recorded", &recorded_route);
+
+    let mut builder = warp::http::Response::builder().status(status);
+    for (k, v) in &headers_map {
+        // Let warp recompute framing headers for the re-serialized body.
+        if k.eq_ignore_ascii_case("content-length") || k.eq_ignore_ascii_case("transfer-encoding") {
+            continue;
+        }
+        builder = builder.header(k, v);
+    }
+    builder = builder.header("Access-Control-Allow-Origin", "*");
+    builder.body(resp_body).unwrap_or_else(|_| warp::http::Response::new("Error building response".into()))
+}
+
+// Build a 502 response for proxy failures.
+fn proxy_error(message: &str) -> warp::http::Response<String> {
+    warp::http::Response::builder()
+        .status(502)
+        .header("Access-Control-Allow-Origin", "*")
+        .body(format!(r#"{{"error": "bad_gateway", "message": "{}"}}"#, message))
+        .unwrap()
 }
 
 // Helper function to validate auth headers - supports ALL auth types
-fn validate_auth(route: &MockRoute, req_headers: &warp::http::HeaderMap) -> bool {
+fn validate_auth(
+    route: &MockRoute,
+    req_headers: &warp::http::HeaderMap,
+    method: &str,
+    path: &str,
+    query: &str,
+    body: &[u8],
+    host: &str,
+    port: u16,
+    nonces: &Arc<Mutex<HashSet<String>>>,
+) -> AuthOutcome {
     let auth_type = route.auth_type.as_deref().unwrap_or("none");
-    
+
     if auth_type == "none" {
-        return true;
+        return AuthOutcome::Allow;
     }
-    
+
     let auth_config = match &route.auth_config {
         Some(config) => config,
-        None => return true, // No config means no validation
+        None => return AuthOutcome::Allow, // No config means no validation
     };
-    
+
     match auth_type {
-        "basic" => validate_basic_auth(auth_config, req_headers),
-        "bearer" => validate_bearer_auth(auth_config, req_headers),
-        "api_key" => validate_api_key_auth(auth_config, req_headers),
-        "digest" => validate_digest_auth(auth_config, req_headers),
-        "oauth1" => validate_oauth1_auth(auth_config, req_headers),
-        "oauth2" => validate_oauth2_auth(auth_config, req_headers),
-        "aws" => validate_aws_auth(auth_config, req_headers),
-        "hawk" => validate_hawk_auth(auth_config, req_headers),
-        _ => true
+        "basic" => validate_basic_auth(auth_config, req_headers).into(),
+        "bearer" => validate_bearer_auth(auth_config, req_headers).into(),
+        "api_key" => validate_api_key_auth(auth_config, req_headers).into(),
+        "digest" => validate_digest_auth(auth_config, req_headers, method, nonces),
+        "oauth1" => validate_oauth1_auth(auth_config, req_headers).into(),
+        "oauth2" => validate_oauth2_auth(auth_config, req_headers).into(),
+        "aws" => validate_aws_auth(auth_config, req_headers, method, path, query, body).into(),
+        "hawk" => validate_hawk_auth(auth_config, req_headers, method, path, query, host, port).into(),
+        _ => AuthOutcome::Allow,
     }
 }
 
@@ -298,8 +573,14 @@ fn validate_bearer_auth(config: &AuthConfig, headers: &warp::http::HeaderMap) ->
     }
     
     let token = &auth_header[7..];
+
+    // When a signing secret is configured, treat the bearer token as a JWT minted
+    // by the OAuth2 token endpoint and verify its signature, expiry and scope.
+    if let Some(secret) = config.client_secret.as_deref() {
+        return verify_jwt(token, secret, config.scope.as_deref());
+    }
+
     let expected_token = config.token.as_deref().unwrap_or("");
-    
     token == expected_token
 }
 
@@ -314,14 +595,80 @@ fn validate_api_key_auth(config: &AuthConfig, headers: &warp::http::HeaderMap) -
     actual_key == expected_key
 }
 
-fn validate_digest_auth(_config: &AuthConfig, headers: &warp::http::HeaderMap) -> bool {
-    // Simplified Digest validation - just check if Authorization header with Digest is present
-    // Full Digest auth requires challenge-response which is complex for mock server
+fn validate_digest_auth(
+    config: &AuthConfig,
+    headers: &warp::http::HeaderMap,
+    method: &str,
+    nonces: &Arc<Mutex<HashSet<String>>>,
+) -> AuthOutcome {
+    let realm = config.realm.as_deref().unwrap_or("");
+    let qop = config.qop.as_deref().unwrap_or("auth");
+    let opaque = config.opaque.clone().unwrap_or_else(generate_nonce);
+
+    // Build a fresh challenge, remembering the nonce so the follow-up can be verified.
+    let challenge = || {
+        let nonce = generate_nonce();
+        if let Ok(mut store) = nonces.lock() {
+            store.insert(nonce.clone());
+        }
+        AuthOutcome::Challenge(format!(
+            "Digest realm=\"{}\", qop=\"{}\", nonce=\"{}\", opaque=\"{}\"",
+            realm, qop, nonce, opaque
+        ))
+    };
+
     let auth_header = headers.get("authorization")
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
-    
-    auth_header.starts_with("Digest ")
+
+    let params = match auth_header.strip_prefix("Digest ") {
+        Some(p) => parse_digest_params(p),
+        None => return challenge(),
+    };
+
+    let get = |k: &str| params.get(k).map(|s| s.as_str()).unwrap_or("");
+    let (username, nonce, uri, nc, cnonce, client_qop, response) = (
+        get("username"), get("nonce"), get("uri"), get("nc"),
+        get("cnonce"), get("qop"), get("response"),
+    );
+
+    // Reject unknown or already-consumed nonces, then burn the nonce to stop replays.
+    {
+        let mut store = match nonces.lock() {
+            Ok(s) => s,
+            Err(_) => return AuthOutcome::Deny,
+        };
+        if !store.remove(nonce) {
+            return challenge();
+        }
+    }
+
+    let password = config.password.as_deref().unwrap_or("");
+    let ha1 = format!("{:x}", md5::compute(format!("{}:{}:{}", username, realm, password).as_bytes()));
+    let ha2 = format!("{:x}", md5::compute(format!("{}:{}", method, uri).as_bytes()));
+    let expected = format!(
+        "{:x}",
+        md5::compute(format!("{}:{}:{}:{}:{}:{}", ha1, nonce, nc, cnonce, client_qop, ha2).as_bytes())
+    );
+
+    if constant_time_eq(expected.as_bytes(), response.as_bytes()) {
+        AuthOutcome::Allow
+    } else {
+        challenge()
+    }
+}
+
+// Parse the comma-separated `key=value` (optionally quoted) pairs of a digest header.
+fn parse_digest_params(raw: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for part in raw.split(',') {
+        let part = part.trim();
+        if let Some((k, v)) = part.split_once('=') {
+            let v = v.trim().trim_matches('"');
+            map.insert(k.trim().to_string(), v.to_string());
+        }
+    }
+    map
 }
 
 fn validate_oauth1_auth(_config: &AuthConfig, headers: &warp::http::HeaderMap) -> bool {
@@ -339,23 +686,455 @@ fn validate_oauth2_auth(config: &AuthConfig, headers: &warp::http::HeaderMap) ->
     validate_bearer_auth(config, headers)
 }
 
-fn validate_aws_auth(_config: &AuthConfig, headers: &warp::http::HeaderMap) -> bool {
-    // Simplified AWS validation - check for AWS4-HMAC-SHA256 Authorization header
-    // Full AWS signature validation is very complex
+fn validate_aws_auth(
+    config: &AuthConfig,
+    headers: &warp::http::HeaderMap,
+    method: &str,
+    path: &str,
+    query: &str,
+    body: &[u8],
+) -> bool {
+    // Full AWS Signature V4 verification against the configured credentials.
     let auth_header = headers.get("authorization")
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
-    
-    auth_header.starts_with("AWS4-HMAC-SHA256")
+
+    let rest = match auth_header.strip_prefix("AWS4-HMAC-SHA256 ") {
+        Some(r) => r,
+        None => return false,
+    };
+
+    // Parse the three comma-separated components of the header.
+    let mut credential = "";
+    let mut signed_headers = "";
+    let mut signature = "";
+    for part in rest.split(',') {
+        let part = part.trim();
+        if let Some(v) = part.strip_prefix("Credential=") {
+            credential = v;
+        } else if let Some(v) = part.strip_prefix("SignedHeaders=") {
+            signed_headers = v;
+        } else if let Some(v) = part.strip_prefix("Signature=") {
+            signature = v;
+        }
+    }
+
+    // Credential = <access_key>/<date>/<region>/<service>/aws4_request
+    let cred_parts: Vec<&str> = credential.split('/').collect();
+    if cred_parts.len() != 5 {
+        return false;
+    }
+    let (access_key, date, region, service) = (cred_parts[0], cred_parts[1], cred_parts[2], cred_parts[3]);
+
+    // The credential scope must line up with the configured values.
+    if access_key != config.access_key.as_deref().unwrap_or("") {
+        return false;
+    }
+    if let Some(expected) = config.region.as_deref() {
+        if region != expected {
+            return false;
+        }
+    }
+    if let Some(expected) = config.service.as_deref() {
+        if service != expected {
+            return false;
+        }
+    }
+
+    // Canonical query string: sort the parameters by name.
+    let mut query_params: Vec<&str> = if query.is_empty() {
+        Vec::new()
+    } else {
+        query.split('&').collect()
+    };
+    query_params.sort_by(|a, b| {
+        let name = |p: &str| p.split('=').next().unwrap_or("").to_string();
+        name(*a).cmp(&name(*b)).then(a.cmp(b))
+    });
+    let canonical_query = query_params.join("&");
+
+    // Canonical headers are the signed headers lowercased, trimmed and sorted.
+    let mut header_names: Vec<String> = signed_headers
+        .split(';')
+        .map(|n| n.trim().to_lowercase())
+        .collect();
+    header_names.sort();
+    let mut canonical_headers = String::new();
+    for name in &header_names {
+        let value = headers.get(name.as_str())
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .trim();
+        canonical_headers.push_str(&format!("{}:{}\n", name, value));
+    }
+
+    let payload_hash = sha256_hex(body);
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method, path, canonical_query, canonical_headers, signed_headers, payload_hash
+    );
+
+    // The amz-date drives the string-to-sign; fall back to the date from the scope.
+    let amz_date = headers.get("x-amz-date")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or(date);
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date, scope, sha256_hex(canonical_request.as_bytes())
+    );
+
+    // Derive the signing key chain and compute the expected signature.
+    let secret = config.secret_key.as_deref().unwrap_or("");
+    let k_date = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let expected = to_hex(&hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+    constant_time_eq(expected.as_bytes(), signature.as_bytes())
 }
 
-fn validate_hawk_auth(_config: &AuthConfig, headers: &warp::http::HeaderMap) -> bool {
-    // Simplified Hawk validation - check for Hawk Authorization header
+fn validate_hawk_auth(
+    config: &AuthConfig,
+    headers: &warp::http::HeaderMap,
+    method: &str,
+    path: &str,
+    query: &str,
+    host: &str,
+    port: u16,
+) -> bool {
     let auth_header = headers.get("authorization")
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
-    
-    auth_header.starts_with("Hawk ")
+
+    let raw = match auth_header.strip_prefix("Hawk ") {
+        Some(r) => r,
+        None => return false,
+    };
+
+    // Hawk fields are the same comma-separated quoted pairs used by digest.
+    let params = parse_digest_params(raw);
+    let get = |k: &str| params.get(k).map(|s| s.as_str()).unwrap_or("");
+    let (id, ts, nonce, mac, hash, ext) = (
+        get("id"), get("ts"), get("nonce"), get("mac"), get("hash"), get("ext"),
+    );
+
+    // The id must match the configured client identifier.
+    if id != config.auth_id.as_deref().unwrap_or("") {
+        return false;
+    }
+
+    // Resource is the request path including any query string.
+    let resource = if query.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}", path, query)
+    };
+
+    let normalized = format!(
+        "hawk.1.header\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n",
+        ts, nonce, method, resource, host, port, hash, ext
+    );
+
+    let auth_key = config.auth_key.as_deref().unwrap_or("");
+    let expected = base64_encode(&hmac_sha256(auth_key.as_bytes(), normalized.as_bytes()));
+
+    constant_time_eq(expected.as_bytes(), mac.as_bytes())
+}
+
+// Lowercase hex encoding of a byte slice
+fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push_str(&format!("{:02x}", b));
+    }
+    out
+}
+
+// Hex-encoded SHA-256 digest
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    to_hex(&hasher.finalize())
+}
+
+// HMAC-SHA256 of `data` under `key`
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+// Constant-time comparison to avoid leaking signature bytes via timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+// Match a request against a route, returning the captured `:param` bindings on success.
+fn match_route(route: &MockRoute, method: &str, path: &str) -> Option<HashMap<String, String>> {
+    if !route.method.eq_ignore_ascii_case(method) {
+        return None;
+    }
+
+    let route_parts: Vec<&str> = route.path.split('/').filter(|s| !s.is_empty()).collect();
+    let req_parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    if route_parts.len() != req_parts.len() {
+        return None;
+    }
+
+    let mut params = HashMap::new();
+    for (route_part, req_part) in route_parts.iter().zip(req_parts.iter()) {
+        if let Some(name) = route_part.strip_prefix(':') {
+            params.insert(name.to_string(), (*req_part).to_string());
+        } else if route_part != req_part {
+            return None;
+        }
+    }
+    Some(params)
+}
+
+// Substitute `{{...}}` placeholders in a response body. Tokens resolve against the
+// captured path params, `query.<name>` for query-string values, and `body.<a.b.c>`
+// for fields dug out of the parsed JSON request body. Unknown tokens render empty.
+fn render_template(
+    template: &str,
+    path_params: &HashMap<String, String>,
+    query: &str,
+    body: &[u8],
+) -> String {
+    let query_params = parse_query(query);
+    let json: Option<serde_json::Value> = serde_json::from_slice(body).ok();
+
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let key = after[..end].trim();
+                out.push_str(&resolve_token(key, path_params, &query_params, &json));
+                rest = &after[end + 2..];
+            }
+            // Unterminated placeholder: emit the remainder verbatim.
+            None => {
+                out.push_str(&rest[start..]);
+                return out;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// Resolve a single template token to its string value.
+fn resolve_token(
+    key: &str,
+    path_params: &HashMap<String, String>,
+    query_params: &HashMap<String, String>,
+    json: &Option<serde_json::Value>,
+) -> String {
+    if let Some(name) = key.strip_prefix("query.") {
+        query_params.get(name).cloned().unwrap_or_default()
+    } else if let Some(path) = key.strip_prefix("body.") {
+        json.as_ref().map(|j| json_path(j, path)).unwrap_or_default()
+    } else {
+        path_params.get(key).cloned().unwrap_or_default()
+    }
+}
+
+// Walk a dotted path (e.g. `user.name`) into a JSON value, rendering the leaf as text.
+fn json_path(value: &serde_json::Value, path: &str) -> String {
+    let mut current = value;
+    for segment in path.split('.') {
+        current = match current.get(segment) {
+            Some(v) => v,
+            None => return String::new(),
+        };
+    }
+    match current {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+// Parse a raw query string into decoded key/value pairs.
+fn parse_query(query: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for pair in query.split('&').filter(|s| !s.is_empty()) {
+        if let Some((k, v)) = pair.split_once('=') {
+            map.insert(form_urldecode(k), form_urldecode(v));
+        } else {
+            map.insert(form_urldecode(pair), String::new());
+        }
+    }
+    map
+}
+
+// Current UNIX time in seconds
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// base64url (no padding) encode/decode, as used by JWT
+fn base64url_encode(input: &[u8]) -> String {
+    use base64::{Engine as _, engine::general_purpose};
+    general_purpose::URL_SAFE_NO_PAD.encode(input)
+}
+
+fn base64url_decode(input: &str) -> Option<Vec<u8>> {
+    use base64::{Engine as _, engine::general_purpose};
+    general_purpose::URL_SAFE_NO_PAD.decode(input).ok()
+}
+
+// Mint an HMAC-SHA256 signed JWT after validating the client credentials from the
+// request body (JSON or form-encoded). Returns the token-response JSON on success.
+fn issue_oauth2_token(config: &AuthConfig, body: &[u8], content_type: &str) -> Result<String, String> {
+    let params = parse_request_params(body, content_type);
+    let get = |k: &str| params.get(k).map(|s| s.as_str()).unwrap_or("");
+
+    let expected_grant = config.grant_type.as_deref().unwrap_or("client_credentials");
+    if get("grant_type") != expected_grant {
+        return Err("unsupported_grant_type".to_string());
+    }
+    if get("client_id") != config.client_id.as_deref().unwrap_or("")
+        || get("client_secret") != config.client_secret.as_deref().unwrap_or("")
+    {
+        return Err("invalid_client".to_string());
+    }
+
+    let secret = config.client_secret.as_deref().unwrap_or("");
+    let ttl = config.token_ttl.unwrap_or(3600);
+    let scope = config.scope.as_deref().unwrap_or("");
+    let exp = now_secs() + ttl;
+
+    let header = base64url_encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+    let payload = base64url_encode(
+        format!(
+            r#"{{"client_id":"{}","scope":"{}","iat":{},"exp":{}}}"#,
+            config.client_id.as_deref().unwrap_or(""), scope, now_secs(), exp
+        )
+        .as_bytes(),
+    );
+    let signing_input = format!("{}.{}", header, payload);
+    let signature = base64url_encode(&hmac_sha256(secret.as_bytes(), signing_input.as_bytes()));
+    let token = format!("{}.{}", signing_input, signature);
+
+    Ok(format!(
+        r#"{{"access_token":"{}","token_type":"Bearer","expires_in":{},"scope":"{}"}}"#,
+        token, ttl, scope
+    ))
+}
+
+// Verify a JWT's signature, reject if expired, and require the given scopes (if any).
+fn verify_jwt(token: &str, secret: &str, required_scope: Option<&str>) -> bool {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return false;
+    }
+    let signing_input = format!("{}.{}", parts[0], parts[1]);
+    let expected_sig = base64url_encode(&hmac_sha256(secret.as_bytes(), signing_input.as_bytes()));
+    if !constant_time_eq(expected_sig.as_bytes(), parts[2].as_bytes()) {
+        return false;
+    }
+
+    let payload_bytes = match base64url_decode(parts[1]) {
+        Some(b) => b,
+        None => return false,
+    };
+    let payload: serde_json::Value = match serde_json::from_slice(&payload_bytes) {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+
+    // Reject tokens whose expiry is in the past.
+    if let Some(exp) = payload.get("exp").and_then(|v| v.as_u64()) {
+        if exp < now_secs() {
+            return false;
+        }
+    }
+
+    // Every required scope must be present in the token's scope claim.
+    if let Some(required) = required_scope.filter(|s| !s.is_empty()) {
+        let granted = payload.get("scope").and_then(|v| v.as_str()).unwrap_or("");
+        let granted_set: HashSet<&str> = granted.split_whitespace().collect();
+        if !required.split_whitespace().all(|s| granted_set.contains(s)) {
+            return false;
+        }
+    }
+
+    true
+}
+
+// Parse request parameters from a JSON object or a form-urlencoded body.
+fn parse_request_params(body: &[u8], content_type: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    if content_type.contains("application/json") {
+        if let Ok(serde_json::Value::Object(obj)) = serde_json::from_slice::<serde_json::Value>(body) {
+            for (k, v) in obj {
+                let val = match v {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                map.insert(k, val);
+            }
+        }
+    } else {
+        let raw = String::from_utf8_lossy(body);
+        for pair in raw.split('&') {
+            if let Some((k, v)) = pair.split_once('=') {
+                map.insert(
+                    form_urldecode(k),
+                    form_urldecode(v),
+                );
+            }
+        }
+    }
+    map
+}
+
+// Minimal application/x-www-form-urlencoded decoding (`+` to space, `%XX` escapes).
+fn form_urldecode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hi = (bytes[i + 1] as char).to_digit(16);
+                let lo = (bytes[i + 2] as char).to_digit(16);
+                if let (Some(hi), Some(lo)) = (hi, lo) {
+                    out.push((hi * 16 + lo) as u8);
+                    i += 2;
+                } else {
+                    out.push(bytes[i]);
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// Simple base64 encode helper
+fn base64_encode(input: &[u8]) -> String {
+    use base64::{Engine as _, engine::general_purpose};
+    general_purpose::STANDARD.encode(input)
 }
 
 // Simple base64 decode helper